@@ -0,0 +1,236 @@
+use crate::config::{self, ConfigError, RecordTarget};
+use crate::dns_record_manager::{DnsRecord, DnsRecordManager, DnsRecordManagerError};
+use log::error;
+use reqwest::{Client, Response};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::json;
+use std::env;
+
+const DEFAULT_BASE_PATH: &str = "https://api.cloudflare.com/client/v4";
+
+#[derive(Debug)]
+pub struct CloudflareConfig {
+    pub api_token: String,
+    pub base_path: String,
+    pub records: Vec<RecordTarget>,
+    pub verify_propagation: bool,
+}
+
+impl CloudflareConfig {
+    pub fn load() -> Result<Self, ConfigError> {
+        let api_token =
+            env::var("API_TOKEN").map_err(|_| ConfigError::MissingEnvironmentVariables)?;
+        let base_path =
+            env::var("BASE_PATH").unwrap_or_else(|_| DEFAULT_BASE_PATH.to_string());
+
+        Ok(Self {
+            api_token,
+            base_path,
+            records: config::load_records()?,
+            verify_propagation: config::load_verify_propagation(),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CfMessage {
+    code: i64,
+    message: String,
+}
+
+/// Cloudflare's `{ success, errors, messages, result }` response envelope.
+#[derive(Debug, Deserialize)]
+struct CfResponse<T> {
+    success: bool,
+    #[serde(default)]
+    errors: Vec<CfMessage>,
+    #[serde(default)]
+    messages: Vec<CfMessage>,
+    result: Option<T>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CfRecord {
+    id: String,
+    name: String,
+    #[serde(rename = "type")]
+    record_type: String,
+    content: String,
+    #[serde(default)]
+    ttl: Option<u32>,
+}
+
+/// Unwraps Cloudflare's response envelope, turning `success: false` (or a
+/// non-2xx status) into an `ApiError` carrying Cloudflare's own error
+/// code/message instead of the raw response text.
+async fn parse_response<T: DeserializeOwned>(
+    response: Response,
+) -> Result<T, DnsRecordManagerError> {
+    let status_ok = response.status().is_success();
+    let body = response
+        .text()
+        .await
+        .map_err(|e| DnsRecordManagerError::RequestFail(anyhow::anyhow!(e)))?;
+    let envelope: CfResponse<T> = serde_json::from_str(&body)
+        .map_err(|e| DnsRecordManagerError::FailToParseResponse(anyhow::anyhow!(e)))?;
+
+    if !status_ok || !envelope.success {
+        let message = envelope
+            .errors
+            .iter()
+            .chain(envelope.messages.iter())
+            .map(|m| m.message.clone())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let code = envelope
+            .errors
+            .first()
+            .map(|m| m.code.to_string())
+            .unwrap_or_default();
+
+        return Err(DnsRecordManagerError::ApiError { code, message });
+    }
+
+    envelope
+        .result
+        .ok_or_else(|| DnsRecordManagerError::Unexpected("Cloudflare response missing result".to_string()))
+}
+
+/// A Cloudflare-backed `DnsRecordManager`. `RecordTarget::domain` holds the
+/// zone id and `RecordTarget::name` holds the record's full hostname, since
+/// Cloudflare has no apex shorthand like Godaddy's `@`.
+pub struct CloudflareDnsRecordManager {
+    config: CloudflareConfig,
+    client: Client,
+}
+
+impl CloudflareDnsRecordManager {
+    pub fn new() -> Result<Self, ConfigError> {
+        let config = CloudflareConfig::load()?;
+        Ok(Self {
+            config,
+            client: Client::new(),
+        })
+    }
+
+    async fn find_record(&self, record: &RecordTarget) -> Result<CfRecord, DnsRecordManagerError> {
+        let response = self
+            .client
+            .get(format!(
+                "{}/zones/{}/dns_records",
+                self.config.base_path, record.domain
+            ))
+            .query(&[
+                ("type", record.record_type.as_str()),
+                ("name", record.name.as_str()),
+            ])
+            .bearer_auth(&self.config.api_token)
+            .send()
+            .await
+            .map_err(|e| DnsRecordManagerError::RequestFail(anyhow::anyhow!(e)))?;
+
+        let records: Vec<CfRecord> = parse_response(response).await?;
+
+        records.into_iter().next().ok_or_else(|| {
+            DnsRecordManagerError::Unexpected("Cannot find DNS record.".to_string())
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl DnsRecordManager for CloudflareDnsRecordManager {
+    fn records(&self) -> &[RecordTarget] {
+        &self.config.records
+    }
+
+    fn verify_propagation(&self) -> bool {
+        self.config.verify_propagation
+    }
+
+    async fn get_arecord_detail(
+        &self,
+        record: &RecordTarget,
+    ) -> Result<DnsRecord, DnsRecordManagerError> {
+        let cf_record = self.find_record(record).await?;
+        Ok(DnsRecord {
+            name: Some(cf_record.name),
+            record_type: Some(cf_record.record_type),
+            data: cf_record.content,
+            ttl: cf_record.ttl,
+        })
+    }
+
+    async fn update_arecord_detail(
+        &self,
+        record: &RecordTarget,
+        new_ip: &str,
+    ) -> Result<(), DnsRecordManagerError> {
+        let existing = self.find_record(record).await?;
+
+        let response = self
+            .client
+            .put(format!(
+                "{}/zones/{}/dns_records/{}",
+                self.config.base_path, record.domain, existing.id
+            ))
+            .bearer_auth(&self.config.api_token)
+            .json(&json!({
+                "type": record.record_type,
+                "name": record.name,
+                "content": new_ip,
+                "ttl": record.ttl,
+            }))
+            .send()
+            .await
+            .map_err(|e| DnsRecordManagerError::RequestFail(anyhow::anyhow!(e)))?;
+
+        parse_response::<CfRecord>(response).await.map_err(|e| {
+            error!("Error response when updating {}: {}", record.key(), e);
+            e
+        })?;
+
+        Ok(())
+    }
+
+    async fn delete_record(&self, record: &RecordTarget) -> Result<(), DnsRecordManagerError> {
+        let existing = self.find_record(record).await?;
+
+        let response = self
+            .client
+            .delete(format!(
+                "{}/zones/{}/dns_records/{}",
+                self.config.base_path, record.domain, existing.id
+            ))
+            .bearer_auth(&self.config.api_token)
+            .send()
+            .await
+            .map_err(|e| DnsRecordManagerError::RequestFail(anyhow::anyhow!(e)))?;
+
+        parse_response::<serde_json::Value>(response).await?;
+
+        Ok(())
+    }
+
+    async fn list_records(&self, domain: &str) -> Result<Vec<DnsRecord>, DnsRecordManagerError> {
+        let response = self
+            .client
+            .get(format!("{}/zones/{}/dns_records", self.config.base_path, domain))
+            .bearer_auth(&self.config.api_token)
+            .send()
+            .await
+            .map_err(|e| DnsRecordManagerError::RequestFail(anyhow::anyhow!(e)))?;
+
+        let records: Vec<CfRecord> = parse_response(response).await?;
+
+        Ok(records
+            .into_iter()
+            .map(|r| DnsRecord {
+                name: Some(r.name),
+                record_type: Some(r.record_type),
+                data: r.content,
+                ttl: r.ttl,
+            })
+            .collect())
+    }
+}
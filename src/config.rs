@@ -0,0 +1,135 @@
+use serde::Deserialize;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+fn default_name() -> String {
+    "@".to_string()
+}
+
+fn default_record_type() -> String {
+    "A".to_string()
+}
+
+fn default_ttl() -> u32 {
+    600
+}
+
+/// A single DNS record we should keep pointed at the current public IP.
+/// `domain` doubles as the provider's zone identifier (Godaddy's domain
+/// name, Cloudflare's zone id) so the same config shape works everywhere.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RecordTarget {
+    pub domain: String,
+    #[serde(default = "default_name")]
+    pub name: String,
+    #[serde(default = "default_record_type")]
+    pub record_type: String,
+    #[serde(default = "default_ttl")]
+    pub ttl: u32,
+}
+
+impl RecordTarget {
+    /// Uniquely identifies this target among the records we manage.
+    pub fn key(&self) -> String {
+        format!("{}/{}/{}", self.domain, self.record_type, self.name)
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct FileConfig {
+    #[serde(default)]
+    records: Vec<RecordTarget>,
+    #[serde(default)]
+    provider: Option<Provider>,
+}
+
+/// Reads the config file (if any) via `config_file_path`, returning the
+/// default `FileConfig` when none is present. Shared by every caller that
+/// needs a setting out of the config file.
+fn read_file_config() -> Result<FileConfig, ConfigError> {
+    match config_file_path() {
+        Some(path) if path.is_file() => {
+            let contents =
+                fs::read_to_string(&path).map_err(|e| ConfigError::InvalidConfigFile(e.to_string()))?;
+            toml::from_str(&contents).map_err(|e| ConfigError::InvalidConfigFile(e.to_string()))
+        }
+        _ => Ok(FileConfig::default()),
+    }
+}
+
+/// Which registrar's API the daemon should talk to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Provider {
+    Godaddy,
+    Cloudflare,
+}
+
+impl Provider {
+    /// Resolves the active provider, preferring (in order) an explicit CLI
+    /// flag, the config file's `provider` field, the `PROVIDER` environment
+    /// variable, and finally Godaddy to match this tool's original behavior.
+    pub fn resolve(cli_override: Option<Provider>) -> Result<Self, ConfigError> {
+        if let Some(provider) = cli_override {
+            return Ok(provider);
+        }
+
+        if let Some(provider) = read_file_config()?.provider {
+            return Ok(provider);
+        }
+
+        match env::var("PROVIDER") {
+            Ok(p) if p.eq_ignore_ascii_case("cloudflare") => Ok(Provider::Cloudflare),
+            Ok(p) if p.eq_ignore_ascii_case("godaddy") => Ok(Provider::Godaddy),
+            Ok(p) => Err(ConfigError::UnknownProvider(p)),
+            Err(_) => Ok(Provider::Godaddy),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ConfigError {
+    #[error("Required environment variables are not set")]
+    MissingEnvironmentVariables,
+    #[error("Unable to read config file: {0}")]
+    InvalidConfigFile(String),
+    #[error("Unknown provider: {0} (expected \"godaddy\" or \"cloudflare\")")]
+    UnknownProvider(String),
+}
+
+/// Loads the records to manage from a TOML config file, discovered via
+/// `CONFIG_PATH` or the standard per-user config directory, falling back to
+/// the single apex record described by `RECORD_NAME` when no file is
+/// present. Shared by every provider's config loader.
+pub fn load_records() -> Result<Vec<RecordTarget>, ConfigError> {
+    let file_config = read_file_config()?;
+    if !file_config.records.is_empty() {
+        return Ok(file_config.records);
+    }
+
+    let record_name = env::var("RECORD_NAME").map_err(|_| ConfigError::MissingEnvironmentVariables)?;
+
+    Ok(vec![RecordTarget {
+        domain: record_name,
+        name: default_name(),
+        record_type: default_record_type(),
+        ttl: default_ttl(),
+    }])
+}
+
+/// Reads `VERIFY_PROPAGATION` from the environment. Shared by every
+/// provider's config loader.
+pub fn load_verify_propagation() -> bool {
+    env::var("VERIFY_PROPAGATION")
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+fn config_file_path() -> Option<PathBuf> {
+    if let Ok(path) = env::var("CONFIG_PATH") {
+        return Some(PathBuf::from(path));
+    }
+
+    dirs::config_dir().map(|dir| dir.join("godaddy-dynamic-ip").join("config.toml"))
+}
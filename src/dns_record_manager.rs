@@ -1,204 +1,148 @@
-use log::{error, info};
-use reqwest::Client;
+use crate::config::RecordTarget;
+use log::info;
 use serde::{Deserialize, Serialize};
-use serde_json::json;
-use std::env;
-
-#[derive(Debug)]
-pub struct GodaddyConfig {
-    pub api_key: String,
-    pub api_secret: String,
-    pub base_path: String,
-    pub record_name: String,
-}
-
-#[derive(thiserror::Error, Debug)]
-pub enum GodaddyConfigError {
-    #[error("Environmental variables are not set: API_KEY, API_SECRET, BASE_PATH, RECORD_NAME")]
-    MissingEnvironmentVariables,
-}
-
-impl GodaddyConfig {
-    pub fn load() -> Result<Self, GodaddyConfigError> {
-        match (
-            env::var("API_KEY"),
-            env::var("API_SECRET"),
-            env::var("BASE_PATH"),
-            env::var("RECORD_NAME"),
-        ) {
-            (Ok(k), Ok(s), Ok(p), Ok(n)) => Ok(Self {
-                api_secret: s.to_string(),
-                api_key: k.to_string(),
-                base_path: p.to_string(),
-                record_name: n.to_string(),
-            }),
-            (_, _, _, _) => Err(GodaddyConfigError::MissingEnvironmentVariables),
-        }
-    }
-}
+use std::collections::HashMap;
+use std::time::Duration;
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct DnsRecord {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default, rename = "type")]
+    pub record_type: Option<String>,
     pub data: String,
+    #[serde(default)]
+    pub ttl: Option<u32>,
 }
 
 #[derive(thiserror::Error, Debug)]
 pub enum DnsRecordManagerError {
-    #[error("Unable to get public IP")]
-    UnableToGetPublicIp,
     #[error("Unable to send request.")]
     RequestFail(anyhow::Error),
     #[error("Fail to parse reponse: {0}")]
     FailToParseResponse(anyhow::Error),
-    #[error("Error response when update record: {0}")]
-    UpdateRecordError(anyhow::Error),
+    #[error("Provider API error {code}: {message}")]
+    ApiError { code: String, message: String },
+    #[error("{0} did not propagate to the expected address in time")]
+    PropagationTimedOut(String),
     #[error("Unexpected error: {0}")]
     Unexpected(String),
 }
 
+/// A seam over a registrar's API: every provider (Godaddy, Cloudflare, ...)
+/// implements this the same way, keeping its own auth scheme and URL
+/// templating behind the impl so the daemon loop in `main` stays
+/// provider-agnostic. `records`/`verify_propagation` expose just enough of
+/// the provider's config for `sync_records` to drive the watch loop.
 #[async_trait::async_trait]
 pub trait DnsRecordManager {
-    async fn get_current_public_ip() -> Result<String, DnsRecordManagerError>;
+    fn records(&self) -> &[RecordTarget];
 
-    async fn get_arecord_detail(&self) -> Result<DnsRecord, DnsRecordManagerError>;
+    fn verify_propagation(&self) -> bool;
 
-    async fn update_arecord_detail(&self, new_ip: &str) -> Result<(), DnsRecordManagerError>;
+    async fn get_arecord_detail(&self, record: &RecordTarget)
+        -> Result<DnsRecord, DnsRecordManagerError>;
 
-    async fn run(&mut self) -> Result<(), DnsRecordManagerError>;
-}
+    async fn update_arecord_detail(
+        &self,
+        record: &RecordTarget,
+        new_ip: &str,
+    ) -> Result<(), DnsRecordManagerError>;
 
-pub struct GodaddyDnsRecordManager {
-    config: GodaddyConfig,
-    client: Client,
-    current_record: Option<DnsRecord>,
-}
+    async fn delete_record(&self, record: &RecordTarget) -> Result<(), DnsRecordManagerError>;
 
-impl GodaddyDnsRecordManager {
-    pub fn new() -> Result<Self, GodaddyConfigError> {
-        let config = GodaddyConfig::load()?;
-        let client = Client::new();
-        Ok(Self {
-            config,
-            client,
-            current_record: None,
-        })
-    }
+    async fn list_records(&self, domain: &str) -> Result<Vec<DnsRecord>, DnsRecordManagerError>;
 }
 
-#[async_trait::async_trait]
-impl DnsRecordManager for GodaddyDnsRecordManager {
-    async fn get_current_public_ip() -> Result<String, DnsRecordManagerError> {
-        if let Some(ip) = public_ip::addr().await {
-            Ok(ip.to_string())
-        } else {
-            Err(DnsRecordManagerError::UnableToGetPublicIp)
-        }
-    }
-
-    async fn get_arecord_detail(&self) -> Result<DnsRecord, DnsRecordManagerError> {
-        let req = self
-            .client
-            .get(format!(
-                "{}/domains/{}/records/A/%40",
-                self.config.base_path, self.config.record_name
-            ))
-            .header(
-                "Authorization",
-                format!("sso-key {}:{}", self.config.api_key, self.config.api_secret),
-            );
-
-        let response = req
-            .send()
-            .await
-            .map_err(|e| DnsRecordManagerError::RequestFail(anyhow::anyhow!(e)))?;
-
-        if !response.status().is_success() {
-            return Err(DnsRecordManagerError::Unexpected(format!(
-                "Error response from Godaddy: {}",
-                response
-                    .text()
-                    .await
-                    .map_err(|_e| DnsRecordManagerError::Unexpected(
-                        "Reponse is not text".to_string()
-                    ))?
-            )));
+/// Brings every configured record in line with the host's current public IP.
+/// Shared by every provider so this orchestration (family dispatch, caching,
+/// verification) lives in one place instead of being copied into each
+/// provider's own loop. `current_records` is a cache of last-known record
+/// values that the caller keeps across ticks, so an already-up-to-date
+/// record isn't re-fetched every time.
+pub async fn sync_records<M: DnsRecordManager>(
+    manager: &M,
+    current_records: &mut HashMap<String, DnsRecord>,
+) -> Result<(), DnsRecordManagerError> {
+    let ipv4 = crate::net::current_public_ipv4().await;
+    let ipv6 = crate::net::current_public_ipv6().await;
+
+    for record in manager.records() {
+        let key = record.key();
+
+        let current_public_ip = match record.record_type.as_str() {
+            "A" => ipv4.clone(),
+            "AAAA" => ipv6.clone(),
+            other => {
+                info!("Skipping {} because {} is not a supported record type", key, other);
+                continue;
+            }
+        };
+
+        let current_public_ip = match current_public_ip {
+            Some(ip) => ip,
+            None => {
+                info!(
+                    "Skipping {} because no public {} address is currently available",
+                    key, record.record_type
+                );
+                continue;
+            }
+        };
+
+        if !current_records.contains_key(&key) {
+            let detail = manager.get_arecord_detail(record).await?;
+            current_records.insert(key.clone(), detail);
         }
 
-        let dns_records = response
-            .json::<Vec<DnsRecord>>()
-            .await
-            .map_err(|e| DnsRecordManagerError::FailToParseResponse(anyhow::anyhow!(e)))?;
-
-        let dns_record = dns_records.into_iter().next().ok_or_else(|| {
-            DnsRecordManagerError::Unexpected("Cannot find DNS record.".to_string())
+        let current_record = current_records.get(&key).ok_or_else(|| {
+            DnsRecordManagerError::Unexpected("Current record should be set".to_string())
         })?;
 
-        Ok(dns_record)
-    }
-
-    async fn update_arecord_detail(&self, new_ip: &str) -> Result<(), DnsRecordManagerError> {
-        let req = self
-            .client
-            .put(format!(
-                "{}/domains/{}/records/A/%40",
-                self.config.base_path, self.config.record_name
-            ))
-            .header(
-                "Authorization",
-                format!("sso-key {}:{}", self.config.api_key, self.config.api_secret),
-            )
-            .header("content-type", "application/json")
-            .body(
-                json!([{
-                    "data": new_ip,
-                    "ttl": 600
-                }])
-                .to_string(),
+        if current_public_ip.eq(&current_record.data) {
+            info!(
+                "DNS record {} already up to date, does not need to update.",
+                key
             );
+            continue;
+        }
 
-        println!("req is: {:?}", req);
-
-        let response = req
-            .send()
+        info!("DNS record {} out of date", key);
+        manager
+            .update_arecord_detail(record, &current_public_ip)
+            .await?;
+
+        // Write the cache back as soon as the update succeeds, before
+        // verification runs, so a provider that's just slow to propagate
+        // doesn't make us re-issue the same update on every subsequent tick.
+        current_records
+            .get_mut(&key)
+            .ok_or_else(|| {
+                DnsRecordManagerError::Unexpected("Current record should be set".to_string())
+            })?
+            .data = current_public_ip.to_owned();
+
+        info!("Updated DNS record {} to {}", key, current_public_ip);
+
+        if manager.verify_propagation() {
+            if let Err(e) = crate::propagation::verify_propagation(
+                record,
+                &current_public_ip,
+                Duration::from_secs(300),
+            )
             .await
-            .map_err(|e| DnsRecordManagerError::RequestFail(anyhow::anyhow!(e)))?;
-        if !response.status().is_success() {
-            println!("data is {:?}", response.text().await.unwrap());
-            return Err(DnsRecordManagerError::UpdateRecordError(anyhow::anyhow!(
-                "{:?}", ""
-            )));
+            {
+                // Propagation never converged, so the record may not
+                // actually be at current_public_ip despite the API accepting
+                // our update. Drop the cache entry rather than leaving it
+                // marked converged, so the next tick re-fetches the
+                // provider's real state and retries instead of silently
+                // treating a broken record as healthy.
+                current_records.remove(&key);
+                return Err(e);
+            }
         }
-        Ok(())
     }
 
-    async fn run(&mut self) -> Result<(), DnsRecordManagerError> {
-        // check if we have dns record detail yet.
-        if self.current_record.is_none() {
-            let record = self.get_arecord_detail().await?;
-            self.current_record = Some(record);
-        }
-
-        // check current public ip
-        let current_public_ip = Self::get_current_public_ip().await?;
-        let current_record = self.current_record.as_ref().ok_or_else(|| {
-            DnsRecordManagerError::Unexpected("Current record should be set".to_string())
-        })?;
-
-        if !current_public_ip.eq(&current_record.data) {
-            info!("DNS record out of date");
-            self.update_arecord_detail(&current_public_ip).await?;
-            self.current_record
-                .as_mut()
-                .ok_or_else(|| {
-                    DnsRecordManagerError::Unexpected("Current record should be set".to_string())
-                })?
-                .data = current_public_ip.to_owned();
-
-            info!("Updated DNS record A record to {}", current_public_ip);
-        } else {
-            info!("DNS record already update to date, does not need to update.")
-        }
-
-        Ok(())
-    }
+    Ok(())
 }
@@ -0,0 +1,306 @@
+use crate::config::{self, ConfigError, RecordTarget};
+use crate::dns_record_manager::{DnsRecord, DnsRecordManager, DnsRecordManagerError};
+use log::error;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+use std::env;
+
+#[derive(Debug)]
+pub struct GodaddyConfig {
+    pub api_key: String,
+    pub api_secret: String,
+    pub base_path: String,
+    pub records: Vec<RecordTarget>,
+    pub verify_propagation: bool,
+}
+
+impl GodaddyConfig {
+    pub fn load() -> Result<Self, ConfigError> {
+        let (api_key, api_secret, base_path) = match (
+            env::var("API_KEY"),
+            env::var("API_SECRET"),
+            env::var("BASE_PATH"),
+        ) {
+            (Ok(k), Ok(s), Ok(p)) => (k, s, p),
+            (_, _, _) => return Err(ConfigError::MissingEnvironmentVariables),
+        };
+
+        Ok(Self {
+            api_key,
+            api_secret,
+            base_path,
+            records: config::load_records()?,
+            verify_propagation: config::load_verify_propagation(),
+        })
+    }
+}
+
+/// A single offending field in Godaddy's error envelope, e.g. the record
+/// whose TTL was rejected.
+#[derive(Debug, Default, Deserialize)]
+struct ResponseField {
+    #[serde(default)]
+    code: String,
+    #[serde(default)]
+    message: String,
+    #[serde(default)]
+    path: String,
+    #[serde(default, rename = "pathRelated")]
+    path_related: String,
+}
+
+/// Godaddy's JSON error envelope, returned on non-2xx responses.
+#[derive(Debug, Default, Deserialize)]
+struct ResponseError {
+    #[serde(default)]
+    code: String,
+    #[serde(default)]
+    message: String,
+    #[serde(default)]
+    fields: Vec<ResponseField>,
+}
+
+/// Reads a non-success response body and, where possible, turns it into an
+/// `ApiError` carrying Godaddy's own code/message/offending fields instead of
+/// the raw response text.
+async fn parse_api_error(response: reqwest::Response) -> DnsRecordManagerError {
+    let body = match response.text().await {
+        Ok(body) => body,
+        Err(_) => return DnsRecordManagerError::Unexpected("Reponse is not text".to_string()),
+    };
+
+    match serde_json::from_str::<ResponseError>(&body) {
+        Ok(err) => {
+            let offending_fields: Vec<String> = err
+                .fields
+                .iter()
+                .map(|field| {
+                    if field.path_related.is_empty() {
+                        format!("{}: {} ({})", field.path, field.message, field.code)
+                    } else {
+                        format!(
+                            "{} (related: {}): {} ({})",
+                            field.path, field.path_related, field.message, field.code
+                        )
+                    }
+                })
+                .collect();
+
+            let message = if offending_fields.is_empty() {
+                err.message
+            } else {
+                format!("{} [{}]", err.message, offending_fields.join(", "))
+            };
+
+            DnsRecordManagerError::ApiError {
+                code: err.code,
+                message,
+            }
+        }
+        Err(_) => {
+            DnsRecordManagerError::Unexpected(format!("Error response from Godaddy: {}", body))
+        }
+    }
+}
+
+/// Leaves unreserved characters (RFC 3986: alphanumerics and `-_.~`)
+/// untouched and percent-encodes everything else, so the apex marker `@`
+/// becomes `%40` instead of being interpolated into the URL path raw.
+const PATH_SEGMENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+fn encode_path_segment(segment: &str) -> String {
+    utf8_percent_encode(segment, PATH_SEGMENT).to_string()
+}
+
+/// Builds the `/domains/{domain}/records/{type}/{name}` endpoint shared by
+/// get/update/delete, percent-encoding each segment.
+fn record_endpoint(base_path: &str, record: &RecordTarget) -> String {
+    format!(
+        "{}/domains/{}/records/{}/{}",
+        base_path,
+        encode_path_segment(&record.domain),
+        encode_path_segment(&record.record_type),
+        encode_path_segment(&record.name)
+    )
+}
+
+/// Builds the `/domains/{domain}/records` endpoint used by `list_records`.
+fn records_endpoint(base_path: &str, domain: &str) -> String {
+    format!("{}/domains/{}/records", base_path, encode_path_segment(domain))
+}
+
+pub struct GodaddyDnsRecordManager {
+    config: GodaddyConfig,
+    client: Client,
+}
+
+impl GodaddyDnsRecordManager {
+    pub fn new() -> Result<Self, ConfigError> {
+        let config = GodaddyConfig::load()?;
+        let client = Client::new();
+        Ok(Self { config, client })
+    }
+}
+
+#[async_trait::async_trait]
+impl DnsRecordManager for GodaddyDnsRecordManager {
+    fn records(&self) -> &[RecordTarget] {
+        &self.config.records
+    }
+
+    fn verify_propagation(&self) -> bool {
+        self.config.verify_propagation
+    }
+
+    async fn get_arecord_detail(
+        &self,
+        record: &RecordTarget,
+    ) -> Result<DnsRecord, DnsRecordManagerError> {
+        let req = self
+            .client
+            .get(record_endpoint(&self.config.base_path, record))
+            .header(
+                "Authorization",
+                format!("sso-key {}:{}", self.config.api_key, self.config.api_secret),
+            );
+
+        let response = req
+            .send()
+            .await
+            .map_err(|e| DnsRecordManagerError::RequestFail(anyhow::anyhow!(e)))?;
+
+        if !response.status().is_success() {
+            return Err(parse_api_error(response).await);
+        }
+
+        let dns_records = response
+            .json::<Vec<DnsRecord>>()
+            .await
+            .map_err(|e| DnsRecordManagerError::FailToParseResponse(anyhow::anyhow!(e)))?;
+
+        let dns_record = dns_records.into_iter().next().ok_or_else(|| {
+            DnsRecordManagerError::Unexpected("Cannot find DNS record.".to_string())
+        })?;
+
+        Ok(dns_record)
+    }
+
+    async fn update_arecord_detail(
+        &self,
+        record: &RecordTarget,
+        new_ip: &str,
+    ) -> Result<(), DnsRecordManagerError> {
+        let req = self
+            .client
+            .put(record_endpoint(&self.config.base_path, record))
+            .header(
+                "Authorization",
+                format!("sso-key {}:{}", self.config.api_key, self.config.api_secret),
+            )
+            .header("content-type", "application/json")
+            .body(
+                json!([{
+                    "data": new_ip,
+                    "ttl": record.ttl
+                }])
+                .to_string(),
+            );
+
+        let response = req
+            .send()
+            .await
+            .map_err(|e| DnsRecordManagerError::RequestFail(anyhow::anyhow!(e)))?;
+        if !response.status().is_success() {
+            let err = parse_api_error(response).await;
+            error!("Error response when updating {}: {}", record.key(), err);
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    async fn delete_record(&self, record: &RecordTarget) -> Result<(), DnsRecordManagerError> {
+        let req = self
+            .client
+            .delete(record_endpoint(&self.config.base_path, record))
+            .header(
+                "Authorization",
+                format!("sso-key {}:{}", self.config.api_key, self.config.api_secret),
+            );
+
+        let response = req
+            .send()
+            .await
+            .map_err(|e| DnsRecordManagerError::RequestFail(anyhow::anyhow!(e)))?;
+
+        if !response.status().is_success() {
+            return Err(parse_api_error(response).await);
+        }
+
+        Ok(())
+    }
+
+    async fn list_records(&self, domain: &str) -> Result<Vec<DnsRecord>, DnsRecordManagerError> {
+        let req = self
+            .client
+            .get(records_endpoint(&self.config.base_path, domain))
+            .header(
+                "Authorization",
+                format!("sso-key {}:{}", self.config.api_key, self.config.api_secret),
+            );
+
+        let response = req
+            .send()
+            .await
+            .map_err(|e| DnsRecordManagerError::RequestFail(anyhow::anyhow!(e)))?;
+
+        if !response.status().is_success() {
+            return Err(parse_api_error(response).await);
+        }
+
+        response
+            .json::<Vec<DnsRecord>>()
+            .await
+            .map_err(|e| DnsRecordManagerError::FailToParseResponse(anyhow::anyhow!(e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apex_record_name_is_percent_encoded() {
+        let record = RecordTarget {
+            domain: "example.com".to_string(),
+            name: "@".to_string(),
+            record_type: "A".to_string(),
+            ttl: 600,
+        };
+
+        assert_eq!(
+            record_endpoint("https://api.godaddy.com/v1", &record),
+            "https://api.godaddy.com/v1/domains/example.com/records/A/%40"
+        );
+    }
+
+    #[test]
+    fn name_with_reserved_characters_is_percent_encoded() {
+        let record = RecordTarget {
+            domain: "example.com".to_string(),
+            name: "weird name/slash".to_string(),
+            record_type: "A".to_string(),
+            ttl: 600,
+        };
+
+        assert_eq!(
+            record_endpoint("https://api.godaddy.com/v1", &record),
+            "https://api.godaddy.com/v1/domains/example.com/records/A/weird%20name%2Fslash"
+        );
+    }
+}
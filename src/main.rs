@@ -1,24 +1,205 @@
+mod cloudflare;
+mod config;
 mod dns_record_manager;
-use crate::dns_record_manager::{DnsRecordManager, GodaddyDnsRecordManager};
+mod godaddy;
+mod net;
+mod propagation;
+
+use crate::cloudflare::CloudflareDnsRecordManager;
+use crate::config::{Provider, RecordTarget};
+use crate::dns_record_manager::{sync_records, DnsRecord, DnsRecordManager};
+use crate::godaddy::GodaddyDnsRecordManager;
 use anyhow::Context;
-use env_logger;
+use clap::{Parser, Subcommand, ValueEnum};
 use log::error;
+use std::collections::HashMap;
 use std::time::Duration;
 
+#[derive(Parser)]
+#[command(author, version, about = "Keep DNS records pointed at your current public IP")]
+struct Cli {
+    /// Registrar to talk to, overriding the config file and `PROVIDER` env var.
+    #[arg(long, value_enum)]
+    provider: Option<ProviderArg>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// CLI-facing mirror of `config::Provider`, needed since `clap::ValueEnum`
+/// can't be derived directly on a type that also derives `serde::Deserialize`
+/// with `rename_all = "lowercase"`.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum ProviderArg {
+    Godaddy,
+    Cloudflare,
+}
+
+impl From<ProviderArg> for Provider {
+    fn from(arg: ProviderArg) -> Self {
+        match arg {
+            ProviderArg::Godaddy => Provider::Godaddy,
+            ProviderArg::Cloudflare => Provider::Cloudflare,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the daemon loop, periodically syncing the configured records.
+    Watch {
+        /// Seconds between update checks.
+        #[arg(long, default_value_t = 600)]
+        interval: u64,
+    },
+    /// Read a record's current value.
+    Get {
+        #[arg(long)]
+        domain: String,
+        #[arg(long, default_value = "@")]
+        name: String,
+        #[arg(long = "type", default_value = "A")]
+        record_type: String,
+    },
+    /// Force a record to a specific value.
+    Set {
+        #[arg(long)]
+        domain: String,
+        #[arg(long, default_value = "@")]
+        name: String,
+        #[arg(long = "type", default_value = "A")]
+        record_type: String,
+        #[arg(long)]
+        value: String,
+        #[arg(long, default_value_t = 600)]
+        ttl: u32,
+    },
+    /// Delete a record.
+    Delete {
+        #[arg(long)]
+        domain: String,
+        #[arg(long, default_value = "@")]
+        name: String,
+        #[arg(long = "type", default_value = "A")]
+        record_type: String,
+    },
+    /// Enumerate all records for a domain.
+    List {
+        #[arg(long)]
+        domain: String,
+    },
+}
+
 #[tokio::main]
 async fn main() -> Result<(), anyhow::Error> {
     env_logger::init();
 
-    let mut interval_timer = tokio::time::interval(Duration::from_secs(600));
-    let mut manager = GodaddyDnsRecordManager::new().context("Create Godaddy Dns manager")?;
+    let cli = Cli::parse();
+    let provider_override = cli.provider.map(Provider::from);
+    let command = cli.command.unwrap_or(Command::Watch { interval: 600 });
+    let provider = Provider::resolve(provider_override).context("Determine active provider")?;
+
+    match provider {
+        Provider::Godaddy => {
+            let manager =
+                GodaddyDnsRecordManager::new().context("Create Godaddy Dns manager")?;
+            run_command(manager, command).await
+        }
+        Provider::Cloudflare => {
+            let manager = CloudflareDnsRecordManager::new()
+                .context("Create Cloudflare Dns manager")?;
+            run_command(manager, command).await
+        }
+    }
+}
 
-    loop {
-        // Wait for the next interval tick
-        interval_timer.tick().await;
+async fn run_command<M: DnsRecordManager>(
+    manager: M,
+    command: Command,
+) -> Result<(), anyhow::Error> {
+    match command {
+        Command::Watch { interval } => {
+            let mut interval_timer = tokio::time::interval(Duration::from_secs(interval));
+            let mut current_records: HashMap<String, DnsRecord> = HashMap::new();
 
-        match manager.run().await {
-            Ok(_) => {}
-            Err(e) => error!("{}", e),
-        };
+            loop {
+                // Wait for the next interval tick
+                interval_timer.tick().await;
+
+                match sync_records(&manager, &mut current_records).await {
+                    Ok(_) => {}
+                    Err(e) => error!("{}", e),
+                };
+            }
+        }
+        Command::Get {
+            domain,
+            name,
+            record_type,
+        } => {
+            let record = RecordTarget {
+                domain,
+                name,
+                record_type,
+                ttl: 600,
+            };
+            let detail = manager
+                .get_arecord_detail(&record)
+                .await
+                .context("Get record")?;
+            println!("{}", detail.data);
+        }
+        Command::Set {
+            domain,
+            name,
+            record_type,
+            value,
+            ttl,
+        } => {
+            let record = RecordTarget {
+                domain,
+                name,
+                record_type,
+                ttl,
+            };
+            manager
+                .update_arecord_detail(&record, &value)
+                .await
+                .context("Update record")?;
+            println!("Updated {} to {}", record.key(), value);
+        }
+        Command::Delete {
+            domain,
+            name,
+            record_type,
+        } => {
+            let record = RecordTarget {
+                domain,
+                name,
+                record_type,
+                ttl: 600,
+            };
+            manager
+                .delete_record(&record)
+                .await
+                .context("Delete record")?;
+            println!("Deleted {}", record.key());
+        }
+        Command::List { domain } => {
+            let records = manager
+                .list_records(&domain)
+                .await
+                .context("List records")?;
+            for record in records {
+                println!(
+                    "{} {} {}",
+                    record.name.unwrap_or_default(),
+                    record.record_type.unwrap_or_default(),
+                    record.data
+                );
+            }
+        }
     }
+
+    Ok(())
 }
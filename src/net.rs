@@ -0,0 +1,9 @@
+/// Resolves the host's current public IPv4 address, if it has one.
+pub async fn current_public_ipv4() -> Option<String> {
+    public_ip::addr_v4().await.map(|ip| ip.to_string())
+}
+
+/// Resolves the host's current public IPv6 address, if it has one.
+pub async fn current_public_ipv6() -> Option<String> {
+    public_ip::addr_v6().await.map(|ip| ip.to_string())
+}
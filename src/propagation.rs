@@ -0,0 +1,104 @@
+use crate::config::RecordTarget;
+use crate::dns_record_manager::DnsRecordManagerError;
+use log::info;
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::time::Duration;
+use tokio::time::sleep;
+use trust_dns_client::client::{Client, SyncClient};
+use trust_dns_client::rr::{DNSClass, Name, RData, RecordType};
+use trust_dns_client::udp::UdpClientConnection;
+
+const PUBLIC_RESOLVER: &str = "8.8.8.8:53";
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Polls a public resolver for `record` until it resolves to exactly
+/// `expected_ip`, retrying with backoff until it converges or `timeout`
+/// elapses. Catches the case where the provider accepted the update but the
+/// change never actually applied.
+///
+/// This won't converge for every record: a hardcoded public resolver and an
+/// "exactly one matching address" check can't see a Cloudflare-proxied
+/// name (the public answer is the proxy's IP, never the origin we pushed)
+/// or any record that legitimately round-robins across multiple A/AAAA
+/// answers. Callers with such records should leave verification off.
+pub async fn verify_propagation(
+    record: &RecordTarget,
+    expected_ip: &str,
+    timeout: Duration,
+) -> Result<(), DnsRecordManagerError> {
+    let fqdn = fully_qualified_name(record);
+    let record_type = match record.record_type.as_str() {
+        "AAAA" => RecordType::AAAA,
+        _ => RecordType::A,
+    };
+
+    let deadline = tokio::time::Instant::now() + timeout;
+    let mut backoff = Duration::from_secs(2);
+
+    loop {
+        let addresses = query_once(fqdn.clone(), record_type).await?;
+
+        if addresses.len() == 1 && addresses[0] == expected_ip {
+            return Ok(());
+        }
+
+        if tokio::time::Instant::now() >= deadline {
+            return Err(DnsRecordManagerError::PropagationTimedOut(fqdn));
+        }
+
+        info!(
+            "{} has not propagated to {} yet (currently {:?}), retrying in {:?}",
+            fqdn, expected_ip, addresses, backoff
+        );
+
+        sleep(backoff).await;
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+async fn query_once(
+    fqdn: String,
+    record_type: RecordType,
+) -> Result<Vec<String>, DnsRecordManagerError> {
+    tokio::task::spawn_blocking(move || -> Result<Vec<String>, DnsRecordManagerError> {
+        let address: SocketAddr = PUBLIC_RESOLVER
+            .parse()
+            .map_err(|e| DnsRecordManagerError::Unexpected(format!("Bad resolver address: {}", e)))?;
+        let conn = UdpClientConnection::new(address)
+            .map_err(|e| DnsRecordManagerError::RequestFail(anyhow::anyhow!(e)))?;
+        let client = SyncClient::new(conn);
+
+        let name = Name::from_str(&fqdn)
+            .map_err(|e| DnsRecordManagerError::Unexpected(format!("Invalid record name: {}", e)))?;
+
+        let response = client
+            .query(&name, DNSClass::IN, record_type)
+            .map_err(|e| DnsRecordManagerError::RequestFail(anyhow::anyhow!(e)))?;
+
+        let addresses = response
+            .answers()
+            .iter()
+            .filter_map(|record| match record.data() {
+                Some(RData::A(ip)) => Some(ip.to_string()),
+                Some(RData::AAAA(ip)) => Some(ip.to_string()),
+                _ => None,
+            })
+            .collect();
+
+        Ok(addresses)
+    })
+    .await
+    .map_err(|e| DnsRecordManagerError::Unexpected(format!("Resolver task panicked: {}", e)))?
+}
+
+fn fully_qualified_name(record: &RecordTarget) -> String {
+    if record.name.contains('.') {
+        // Already a fully-qualified hostname, e.g. Cloudflare's record name.
+        format!("{}.", record.name.trim_end_matches('.'))
+    } else if record.name == "@" {
+        format!("{}.", record.domain)
+    } else {
+        format!("{}.{}.", record.name, record.domain)
+    }
+}